@@ -23,9 +23,14 @@ pub enum Error {
 
     #[error("{0} is not a supported list type must be either: filesystem, snapshot, volume, bookmark or all")]
     InvalidZfsListType(String),
+
+    #[error("{0} is not a valid property source")]
+    InvalidPropertySource(String),
 }
 
+pub mod executor;
 pub mod zfs;
+pub mod zpool;
 
 #[cfg(test)]
 mod tests {
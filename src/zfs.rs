@@ -1,11 +1,21 @@
 use derive_builder::{Builder, UninitializedFieldError};
 use getset::Getters;
 use miette::Diagnostic;
-use std::{collections::HashMap, fmt::Display, str::FromStr};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::Read,
+    process::{ChildStdout, Stdio},
+    str::FromStr,
+};
 use thiserror::Error;
 
-#[cfg(not(test))]
-use std::process::Command;
+use crate::executor::{Binary, Executor};
+
+/// A typed byte size, re-exported so callers can do arithmetic and
+/// comparisons instead of juggling raw strings like `"10G"`
+pub use bytesize::ByteSize;
 
 #[doc = "Error type for All zfs related builders"]
 #[derive(Debug, Error, Diagnostic)]
@@ -39,18 +49,12 @@ impl Display for ZfsBuilderError {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 struct ZfsProperties(HashMap<String, String>);
 
-impl Default for ZfsProperties {
-    fn default() -> Self {
-        Self(HashMap::new())
-    }
-}
-
-impl Into<Vec<String>> for ZfsProperties {
-    fn into(self) -> Vec<String> {
-        self.0
+impl From<ZfsProperties> for Vec<String> {
+    fn from(val: ZfsProperties) -> Self {
+        val.0
             .iter()
             .map(|(key, value)| format!("{}={}", key, value))
             .collect()
@@ -73,19 +77,58 @@ pub struct CreateRequest {
     #[builder(default)]
     recursive: bool,
 
-    /// Humans size of the volume
-    #[builder(setter(into, strip_option), default)]
-    volsize: Option<String>,
+    /// Size of the volume. Accepts a `ByteSize` or a human string such as
+    /// `"10G"`/`"128K"`
+    #[builder(setter(custom), default)]
+    volsize: Option<ByteSize>,
 
-    /// Blocksize of the volume defaults to 128KB
-    #[builder(setter(into, strip_option), default)]
-    blocksize: Option<i32>,
+    /// Raw, not-yet-validated `volsize` input, kept around so `validate()`
+    /// can report a precise parse error
+    #[builder(setter(custom), default)]
+    #[allow(dead_code)]
+    volsize_raw: Option<String>,
+
+    /// Blocksize of the volume, defaults to 128KB. Accepts a `ByteSize` or
+    /// a human string such as `"128K"`
+    #[builder(setter(custom), default)]
+    blocksize: Option<ByteSize>,
+
+    /// Raw, not-yet-validated `blocksize` input, kept around so
+    /// `validate()` can report a precise parse error
+    #[builder(setter(custom), default)]
+    #[allow(dead_code)]
+    blocksize_raw: Option<String>,
 
     /// Choose if to create the volume as sparse
     #[builder(default)]
     sparse: bool,
 }
 
+/// Accepted input for a size-valued builder field: either an exact
+/// `ByteSize` or a human-readable string such as `"10G"` to be parsed
+pub enum SizeInput {
+    Exact(ByteSize),
+    Text(String),
+}
+
+impl From<ByteSize> for SizeInput {
+    fn from(value: ByteSize) -> Self {
+        Self::Exact(value)
+    }
+}
+
+impl From<String> for SizeInput {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for SizeInput {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
 impl CreateRequestBuilder {
     pub fn add_property<S: ToString>(&mut self, key: S, value: S) -> &mut Self {
         if let Some(mut properties) = self.properties.clone() {
@@ -101,6 +144,40 @@ impl CreateRequestBuilder {
         self
     }
 
+    /// Accepts a `ByteSize` or a human size string such as `"10G"`. A
+    /// `ByteSize` is stored as-is; a string is parsed eagerly so
+    /// `validate()` can report a precise error
+    pub fn volsize<S: Into<SizeInput>>(&mut self, value: S) -> &mut Self {
+        match value.into() {
+            SizeInput::Exact(size) => {
+                self.volsize = Some(Some(size));
+                self.volsize_raw = Some(None);
+            }
+            SizeInput::Text(raw) => {
+                self.volsize = Some(raw.parse::<ByteSize>().ok());
+                self.volsize_raw = Some(Some(raw));
+            }
+        }
+        self
+    }
+
+    /// Accepts a `ByteSize` or a human size string such as `"128K"`. A
+    /// `ByteSize` is stored as-is; a string is parsed eagerly so
+    /// `validate()` can report a precise error
+    pub fn blocksize<S: Into<SizeInput>>(&mut self, value: S) -> &mut Self {
+        match value.into() {
+            SizeInput::Exact(size) => {
+                self.blocksize = Some(Some(size));
+                self.blocksize_raw = Some(None);
+            }
+            SizeInput::Text(raw) => {
+                self.blocksize = Some(raw.parse::<ByteSize>().ok());
+                self.blocksize_raw = Some(Some(raw));
+            }
+        }
+        self
+    }
+
     fn validate(&self) -> std::result::Result<(), String> {
         if let Some(name) = &self.name {
             if name.contains("@") {
@@ -108,6 +185,16 @@ impl CreateRequestBuilder {
             }
         }
 
+        if let Some(Some(raw)) = &self.volsize_raw {
+            raw.parse::<ByteSize>()
+                .map_err(|e| format!("invalid volsize '{}': {}", raw, e))?;
+        }
+
+        if let Some(Some(raw)) = &self.blocksize_raw {
+            raw.parse::<ByteSize>()
+                .map_err(|e| format!("invalid blocksize '{}': {}", raw, e))?;
+        }
+
         Ok(())
     }
 }
@@ -128,7 +215,7 @@ pub fn create(req: &CreateRequest) -> crate::Result<Dataset> {
 
         if let Some(blocksize) = &req.blocksize {
             args.push(String::from("-b"));
-            args.push(blocksize.to_string());
+            args.push(blocksize.0.to_string());
         }
     }
 
@@ -139,7 +226,7 @@ pub fn create(req: &CreateRequest) -> crate::Result<Dataset> {
 
     if let Some(volsize) = &req.volsize {
         args.push(String::from("-V"));
-        args.push(volsize.clone());
+        args.push(volsize.0.to_string());
     }
 
     args.push(req.name.clone());
@@ -311,22 +398,21 @@ pub struct ListRequest {
     #[builder(default)]
     recursive: bool,
 
+    /// The ordered list of property names to report, passed to `-o`.
+    /// Defaults to just `name` when empty
     #[builder(setter(custom), default)]
-    properties: ZfsProperties,
+    properties: Vec<String>,
 }
 
 impl ListRequestBuilder {
-    /// define a zfs property that the target dataset|volume should have
-    // this property won't apply to the source
-    pub fn add_property<S: ToString>(&mut self, key: S, value: S) -> &mut Self {
+    /// Add a property name to the `-o` column list, in the order it should
+    /// be reported
+    pub fn add_property<S: ToString>(&mut self, name: S) -> &mut Self {
         if let Some(mut properties) = self.properties.clone() {
-            properties.0.insert(key.to_string(), value.to_string());
+            properties.push(name.to_string());
             self.properties = Some(properties);
         } else {
-            self.properties = Some(ZfsProperties(HashMap::from([(
-                key.to_string(),
-                value.to_string(),
-            )])));
+            self.properties = Some(vec![name.to_string()]);
         }
 
         self
@@ -368,9 +454,9 @@ impl FromStr for ListType {
     }
 }
 
-impl Into<String> for ListType {
-    fn into(self) -> String {
-        String::from(match self {
+impl From<ListType> for String {
+    fn from(val: ListType) -> Self {
+        String::from(match val {
             ListType::FileSystem => "filesystem",
             ListType::Snapshot => "snapshot",
             ListType::Volume => "volume",
@@ -380,8 +466,30 @@ impl Into<String> for ListType {
     }
 }
 
-pub fn list(req: &ListRequest) -> crate::Result<Vec<Vec<String>>> {
-    let props: Vec<String> = req.properties.clone().into();
+/// A single row reported by `list`, keyed by the property names that were
+/// requested
+#[derive(Debug, Clone, Serialize)]
+pub struct ListRow(HashMap<String, String>);
+
+impl ListRow {
+    /// The value of `prop` in this row, or `None` if it wasn't requested
+    pub fn get(&self, prop: &str) -> Option<&str> {
+        self.0.get(prop).map(String::as_str)
+    }
+
+    /// Parse a raw-bytes size property (e.g. `used`, `available`, fetched
+    /// via `-p`) into a typed `ByteSize`
+    pub fn get_size(&self, prop: &str) -> Option<ByteSize> {
+        self.get(prop).and_then(|v| v.parse::<u64>().ok()).map(ByteSize)
+    }
+}
+
+pub fn list(req: &ListRequest) -> crate::Result<Vec<ListRow>> {
+    let props = if req.properties.is_empty() {
+        vec![String::from("name")]
+    } else {
+        req.properties.clone()
+    };
 
     let mut args = vec![];
 
@@ -394,13 +502,10 @@ pub fn list(req: &ListRequest) -> crate::Result<Vec<Vec<String>>> {
     }
 
     args.push(String::from("-Hp"));
+    args.push(String::from("-o"));
+    args.push(props.join(","));
 
-    for p in props {
-        args.push(String::from("-o"));
-        args.push(p);
-    }
-
-    if req.list_types.len() > 0 {
+    if !req.list_types.is_empty() {
         args.push(String::from("-t"));
         args.push(
             req.list_types
@@ -415,16 +520,251 @@ pub fn list(req: &ListRequest) -> crate::Result<Vec<Vec<String>>> {
         args.push(root.clone());
     }
 
-    zfs(ZfsCommand::List, args).map(|v| {
-        v.lines()
-            .into_iter()
-            .map(|l| {
-                l.split_whitespace()
-                    .map(|str| str.to_string())
-                    .collect::<Vec<String>>()
-            })
-            .collect()
-    })
+    zfs(ZfsCommand::List, args).map(|v| parse_list_rows(&props, &v))
+}
+
+/// Zip each tab-delimited output line against the ordered property list
+/// that was requested, producing one `ListRow` per line
+fn parse_list_rows(props: &[String], output: &str) -> Vec<ListRow> {
+    output
+        .lines()
+        .map(|l| {
+            let row = props
+                .iter()
+                .cloned()
+                .zip(l.split('\t').map(String::from))
+                .collect::<HashMap<String, String>>();
+            ListRow(row)
+        })
+        .collect()
+}
+
+/// A request to stream a snapshot out with `zfs send`
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate", error = "ZfsBuilderError"))]
+pub struct SendRequest {
+    /// The snapshot to send. Required unless `resume_token` is set
+    #[builder(setter(into, strip_option), default)]
+    snapshot: Option<String>,
+
+    /// Base snapshot (or `#` bookmark) for an incremental stream, mapped to
+    /// `-i` (or `-I` when `replicate` is set, to include intermediary
+    /// snapshots)
+    #[builder(setter(into, strip_option), default)]
+    from: Option<String>,
+
+    /// Resume a previously interrupted receive from this token (`-t`).
+    /// Mutually exclusive with `snapshot`, `from`, `replicate`,
+    /// `include_properties`, `raw` and `large_block` (real `zfs send -t`
+    /// forbids combining flags); when set, only `-t <token>` is emitted
+    #[builder(setter(into, strip_option), default)]
+    resume_token: Option<String>,
+
+    /// Generate a replication stream package (`-R`)
+    #[builder(default)]
+    replicate: bool,
+
+    /// Include dataset properties in the stream (`-p`)
+    #[builder(default)]
+    include_properties: bool,
+
+    /// Generate a raw, still-encrypted send stream (`-w`)
+    #[builder(default)]
+    raw: bool,
+
+    /// Allow blocks larger than 128KB in the stream (`-L`)
+    #[builder(default)]
+    large_block: bool,
+}
+
+impl SendRequestBuilder {
+    fn validate(&self) -> std::result::Result<(), String> {
+        let snapshot = self.snapshot.as_ref().and_then(|o| o.as_ref());
+        let from = self.from.as_ref().and_then(|o| o.as_ref());
+        let resume_token = self.resume_token.as_ref().and_then(|o| o.as_ref());
+
+        if let Some(from) = from {
+            if !from.contains('@') && !from.contains('#') {
+                return Err("Invalid from snapshot or bookmark name".to_string());
+            }
+        }
+
+        if resume_token.is_some() {
+            if from.is_some() {
+                return Err("resume_token is mutually exclusive with from".to_string());
+            }
+            if snapshot.is_some() {
+                return Err("resume_token is mutually exclusive with snapshot".to_string());
+            }
+            if self.replicate.unwrap_or(false)
+                || self.include_properties.unwrap_or(false)
+                || self.raw.unwrap_or(false)
+                || self.large_block.unwrap_or(false)
+            {
+                return Err(
+                    "resume_token is mutually exclusive with replicate/include_properties/raw/large_block"
+                        .to_string(),
+                );
+            }
+        } else if snapshot.is_none() {
+            return Err("snapshot is required unless resume_token is set".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+fn send_args(req: &SendRequest) -> Vec<String> {
+    let mut args = vec![];
+
+    if let Some(token) = &req.resume_token {
+        args.push(String::from("-t"));
+        args.push(token.clone());
+        return args;
+    }
+
+    if req.replicate {
+        args.push(String::from("-R"));
+    }
+
+    if req.include_properties {
+        args.push(String::from("-p"));
+    }
+
+    if req.raw {
+        args.push(String::from("-w"));
+    }
+
+    if req.large_block {
+        args.push(String::from("-L"));
+    }
+
+    if let Some(from) = &req.from {
+        args.push(String::from(if req.replicate { "-I" } else { "-i" }));
+        args.push(from.clone());
+    }
+
+    if let Some(snapshot) = &req.snapshot {
+        args.push(snapshot.clone());
+    }
+
+    args
+}
+
+/// A handle onto a running `zfs send` child process
+pub struct SendStream {
+    child: std::process::Child,
+}
+
+impl SendStream {
+    /// The stdout of the `zfs send` process, carrying the send stream itself
+    pub fn stdout(&mut self) -> &mut ChildStdout {
+        self.child
+            .stdout
+            .as_mut()
+            .expect("send spawned with a piped stdout")
+    }
+
+    /// Wait for the `zfs send` process to exit, surfacing a non-zero exit
+    /// through `Error::ZFSError`
+    pub fn wait(mut self) -> crate::Result<()> {
+        let status = self.child.wait()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            let mut stderr = String::new();
+            if let Some(mut s) = self.child.stderr.take() {
+                s.read_to_string(&mut stderr)?;
+            }
+            Err(crate::Error::ZFSError(stderr))
+        }
+    }
+}
+
+/// Spawn `zfs send` for `req` on the default executor, returning a handle
+/// to the streaming child process rather than waiting for it to finish
+pub fn send(req: &SendRequest) -> crate::Result<SendStream> {
+    send_on(&*crate::executor::default_executor(), req)
+}
+
+/// Like [`send`], but runs on `executor` instead of the configured default
+/// (e.g. to send from a remote host over `ssh`)
+pub fn send_on(executor: &dyn Executor, req: &SendRequest) -> crate::Result<SendStream> {
+    let send_args = send_args(req);
+    let mut args = vec!["send"];
+    args.extend(send_args.iter().map(String::as_str));
+
+    let mut cmd = executor.command(Binary::Zfs, &args);
+
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    let child = cmd.spawn()?;
+
+    Ok(SendStream { child })
+}
+
+/// Receive a stream produced by `zfs send` into `target` on the default
+/// executor, reading the stream from `source`
+pub fn receive(target: &str, source: &mut impl Read) -> crate::Result<Dataset> {
+    receive_on(&*crate::executor::default_executor(), target, source)
+}
+
+/// Like [`receive`], but runs on `executor` instead of the configured
+/// default (e.g. to receive on a remote host over `ssh`)
+pub fn receive_on(
+    executor: &dyn Executor,
+    target: &str,
+    source: &mut impl Read,
+) -> crate::Result<Dataset> {
+    let mut cmd = executor.command(Binary::Zfs, &["receive", target]);
+
+    cmd.stdin(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    let mut child = cmd.spawn()?;
+
+    {
+        let mut stdin = child
+            .stdin
+            .take()
+            .expect("receive spawned with a piped stdin");
+        std::io::copy(source, &mut stdin)?;
+    }
+
+    let output = child.wait_with_output()?;
+
+    if output.status.success() {
+        Ok(Dataset {
+            name: target.to_string(),
+        })
+    } else {
+        Err(crate::Error::ZFSError(String::from_utf8(output.stderr)?))
+    }
+}
+
+/// Pipe a `zfs send` stream directly into `zfs receive <target>`, both on
+/// the default executor, without the caller ever seeing the bytes in
+/// between
+pub fn send_to_receive(req: &SendRequest, target: &str) -> crate::Result<Dataset> {
+    let executor = crate::executor::default_executor();
+    send_to_receive_on(&*executor, req, &*executor, target)
+}
+
+/// Like [`send_to_receive`], but lets the send and receive sides run on
+/// different executors, enabling cross-host replication (e.g. sending
+/// locally and receiving on a remote host over `ssh`)
+pub fn send_to_receive_on(
+    send_executor: &dyn Executor,
+    req: &SendRequest,
+    receive_executor: &dyn Executor,
+    target: &str,
+) -> crate::Result<Dataset> {
+    let mut stream = send_on(send_executor, req)?;
+    let ds = receive_on(receive_executor, target, stream.stdout())?;
+    stream.wait()?;
+    Ok(ds)
 }
 
 enum ZfsCommand {
@@ -437,6 +777,11 @@ enum ZfsCommand {
     Get,
     Snapshot,
     Destroy,
+    Rollback,
+    Hold,
+    Release,
+    Rename,
+    Bookmark,
 }
 
 #[cfg(not(test))]
@@ -445,24 +790,38 @@ where
     I: IntoIterator,
     I::Item: ToString,
 {
-    let mut cmd = Command::new("zfs");
-    match zfs_cmd {
-        ZfsCommand::Create => cmd.arg("create"),
-        ZfsCommand::Clone => cmd.arg("clone"),
-        ZfsCommand::Destroy => cmd.arg("destroy"),
-        ZfsCommand::Promote => cmd.arg("promote"),
-        ZfsCommand::List => cmd.arg("list"),
-        ZfsCommand::Open => cmd.arg("list"),
-        ZfsCommand::Set => cmd.arg("set"),
-        ZfsCommand::Get => cmd.arg("get"),
-        ZfsCommand::Snapshot => cmd.arg("snapshot"),
+    run_zfs(&*crate::executor::default_executor(), zfs_cmd, args)
+}
+
+#[cfg(not(test))]
+fn run_zfs<I>(executor: &dyn Executor, zfs_cmd: ZfsCommand, args: I) -> crate::Result<String>
+where
+    I: IntoIterator,
+    I::Item: ToString,
+{
+    let subcommand = match zfs_cmd {
+        ZfsCommand::Create => "create",
+        ZfsCommand::Clone => "clone",
+        ZfsCommand::Destroy => "destroy",
+        ZfsCommand::Promote => "promote",
+        ZfsCommand::List => "list",
+        ZfsCommand::Open => "list",
+        ZfsCommand::Set => "set",
+        ZfsCommand::Get => "get",
+        ZfsCommand::Snapshot => "snapshot",
+        ZfsCommand::Rollback => "rollback",
+        ZfsCommand::Hold => "hold",
+        ZfsCommand::Release => "release",
+        ZfsCommand::Rename => "rename",
+        ZfsCommand::Bookmark => "bookmark",
     };
 
-    for arg in args {
-        cmd.arg(arg.to_string().as_str());
-    }
+    let arg_strings: Vec<String> = args.into_iter().map(|a| a.to_string()).collect();
+    let mut full_args = vec![subcommand];
+    full_args.extend(arg_strings.iter().map(String::as_str));
+
+    let mut cmd = executor.command(Binary::Zfs, &full_args);
 
-    cmd.env_clear();
     let output = cmd.output()?;
 
     if !output.status.success() {
@@ -488,9 +847,79 @@ where
         ZfsCommand::Set => Ok(String::new()),
         ZfsCommand::Get => Ok(String::from("test_value")),
         ZfsCommand::Snapshot => Ok(String::new()),
+        ZfsCommand::Rollback => Ok(String::new()),
+        ZfsCommand::Hold => Ok(String::new()),
+        ZfsCommand::Release => Ok(String::new()),
+        ZfsCommand::Rename => Ok(String::new()),
+        ZfsCommand::Bookmark => Ok(String::new()),
+    }
+}
+
+/// Where a property's value comes from, as reported by `zfs get`'s
+/// `source` column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertySource {
+    /// Set directly on this dataset/snapshot
+    Local,
+    /// Inherited from the named parent dataset
+    Inherited(String),
+    /// Left at its built-in default
+    Default,
+    /// Carried over from a `zfs receive`
+    Received,
+    /// Not applicable to this property
+    None_,
+}
+
+impl FromStr for PropertySource {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(parent) = s.strip_prefix("inherited from ") {
+            return Ok(Self::Inherited(parent.to_string()));
+        }
+
+        match s {
+            "local" => Ok(Self::Local),
+            "default" => Ok(Self::Default),
+            "received" => Ok(Self::Received),
+            "-" | "" => Ok(Self::None_),
+            x => Err(crate::Error::InvalidPropertySource(x.to_string())),
+        }
     }
 }
 
+/// A single property's value together with where it came from
+#[derive(Debug, Clone)]
+pub struct PropertyValue {
+    pub value: String,
+    pub source: PropertySource,
+}
+
+fn parse_properties(output: &str) -> crate::Result<HashMap<String, PropertyValue>> {
+    let mut properties = HashMap::new();
+
+    for line in output.lines() {
+        let mut fields = line.split('\t');
+        let name = fields.next().unwrap_or_default().to_string();
+        let value = fields.next().unwrap_or_default().to_string();
+        let source = fields.next().unwrap_or_default().parse()?;
+
+        properties.insert(name, PropertyValue { value, source });
+    }
+
+    Ok(properties)
+}
+
+fn get_properties(target: &str, props: &str) -> crate::Result<HashMap<String, PropertyValue>> {
+    let output = zfs(
+        ZfsCommand::Get,
+        &["-Hp", "-o", "property,value,source", props, target],
+    )?;
+
+    parse_properties(&output)
+}
+
 #[derive(Getters, Debug, Clone)]
 pub struct Dataset {
     #[getset(get = "pub")]
@@ -502,6 +931,17 @@ impl Dataset {
         zfs(ZfsCommand::Get, &["-H", "-o", "value", name, &self.name])
     }
 
+    /// Fetch every property of this dataset, with its source
+    pub fn get_all(&self) -> crate::Result<HashMap<String, PropertyValue>> {
+        get_properties(&self.name, "all")
+    }
+
+    /// Fetch a specific set of properties of this dataset, with their
+    /// source
+    pub fn get_many(&self, names: &[&str]) -> crate::Result<HashMap<String, PropertyValue>> {
+        get_properties(&self.name, &names.join(","))
+    }
+
     pub fn set(&self, name: &str, value: &str) -> crate::Result<()> {
         let val_arg = format!("{}={}", name, value);
         zfs(ZfsCommand::Set, &[val_arg.as_str(), &self.name]).map(|_v| ())
@@ -520,7 +960,7 @@ impl Dataset {
     pub fn snapshot(&self, name: &str) -> crate::Result<Snapshot> {
         snapshot(
             &SnapshotRequestBuilder::default()
-                .snapshot(&format!("{}@{}", &self.name, name))
+                .snapshot(format!("{}@{}", &self.name, name))
                 .build()?,
         )
     }
@@ -537,6 +977,17 @@ impl Snapshot {
         zfs(ZfsCommand::Get, &["-H", "-o", "value", name, &self.name])
     }
 
+    /// Fetch every property of this snapshot, with its source
+    pub fn get_all(&self) -> crate::Result<HashMap<String, PropertyValue>> {
+        get_properties(&self.name, "all")
+    }
+
+    /// Fetch a specific set of properties of this snapshot, with their
+    /// source
+    pub fn get_many(&self, names: &[&str]) -> crate::Result<HashMap<String, PropertyValue>> {
+        get_properties(&self.name, &names.join(","))
+    }
+
     pub fn set(&self, name: &str, value: &str) -> crate::Result<()> {
         let val_arg = format!("{}={}", name, value);
         zfs(ZfsCommand::Set, &[val_arg.as_str(), &self.name]).map(|_v| ())
@@ -545,4 +996,336 @@ impl Snapshot {
     pub fn destroy(&self) -> crate::Result<()> {
         zfs(ZfsCommand::Destroy, &[self.name.as_str()]).map(|_v| ())
     }
+
+    /// Roll the dataset back to this snapshot. Set `force_recent` to
+    /// destroy any snapshots and clones created since this one (`-r`/`-R`)
+    pub fn rollback(&self, force_recent: bool) -> crate::Result<()> {
+        let mut args = vec![];
+
+        if force_recent {
+            args.push("-r");
+            args.push("-R");
+        }
+
+        args.push(self.name.as_str());
+
+        zfs(ZfsCommand::Rollback, args).map(|_v| ())
+    }
+
+    /// Place a hold on this snapshot under `tag`, preventing it from being
+    /// destroyed until the hold is released
+    pub fn hold(&self, tag: &str) -> crate::Result<()> {
+        if tag.is_empty() {
+            return Err(crate::Error::ZFSError("hold tag must not be empty".to_string()));
+        }
+
+        zfs(ZfsCommand::Hold, &[tag, self.name.as_str()]).map(|_v| ())
+    }
+
+    /// Release a hold previously placed on this snapshot under `tag`
+    pub fn release(&self, tag: &str) -> crate::Result<()> {
+        if tag.is_empty() {
+            return Err(crate::Error::ZFSError("hold tag must not be empty".to_string()));
+        }
+
+        zfs(ZfsCommand::Release, &[tag, self.name.as_str()]).map(|_v| ())
+    }
+
+    /// Rename this snapshot to `new`, a bare snapshot suffix (the part
+    /// after `@`)
+    pub fn rename(&self, new: &str) -> crate::Result<Snapshot> {
+        let (dataset, _) = self
+            .name
+            .split_once('@')
+            .expect("snapshot names always contain '@'");
+        let new_name = format!("{}@{}", dataset, new);
+
+        zfs(ZfsCommand::Rename, &[self.name.as_str(), new_name.as_str()]).map(|_v| Snapshot {
+            name: new_name.clone(),
+        })
+    }
+
+    /// Create a bookmark of this snapshot, usable as an incremental `send`
+    /// source without keeping the snapshot itself around
+    pub fn bookmark(&self, name: &str) -> crate::Result<Bookmark> {
+        let (dataset, _) = self
+            .name
+            .split_once('@')
+            .expect("snapshot names always contain '@'");
+        let bookmark_name = format!("{}#{}", dataset, name);
+
+        zfs(
+            ZfsCommand::Bookmark,
+            &[self.name.as_str(), bookmark_name.as_str()],
+        )
+        .map(|_v| Bookmark {
+            name: bookmark_name.clone(),
+        })
+    }
+}
+
+#[derive(Getters, Debug, Clone)]
+pub struct Bookmark {
+    #[getset(get = "pub")]
+    name: String,
+}
+
+impl Bookmark {
+    pub fn get(&self, name: &str) -> crate::Result<String> {
+        zfs(ZfsCommand::Get, &["-H", "-o", "value", name, &self.name])
+    }
+
+    pub fn destroy(&self) -> crate::Result<()> {
+        zfs(ZfsCommand::Destroy, &[self.name.as_str()]).map(|_v| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn send_args_plain_snapshot() {
+        let req = SendRequestBuilder::default()
+            .snapshot("tank/ds@a")
+            .build()
+            .unwrap();
+
+        assert_eq!(send_args(&req), vec!["tank/ds@a"]);
+    }
+
+    #[test]
+    fn send_args_incremental() {
+        let req = SendRequestBuilder::default()
+            .snapshot("tank/ds@b")
+            .from("tank/ds@a")
+            .build()
+            .unwrap();
+
+        assert_eq!(send_args(&req), vec!["-i", "tank/ds@a", "tank/ds@b"]);
+    }
+
+    #[test]
+    fn send_args_replicate_uses_capital_i() {
+        let req = SendRequestBuilder::default()
+            .snapshot("tank/ds@b")
+            .from("tank/ds@a")
+            .replicate(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(send_args(&req), vec!["-R", "-I", "tank/ds@a", "tank/ds@b"]);
+    }
+
+    #[test]
+    fn send_args_resume_token_ignores_everything_else() {
+        let req = SendRequestBuilder::default()
+            .resume_token("abc123")
+            .build()
+            .unwrap();
+
+        assert_eq!(send_args(&req), vec!["-t", "abc123"]);
+    }
+
+    #[test]
+    fn send_request_requires_snapshot_or_resume_token() {
+        assert!(SendRequestBuilder::default().build().is_err());
+    }
+
+    #[test]
+    fn send_request_resume_token_excludes_snapshot() {
+        assert!(SendRequestBuilder::default()
+            .snapshot("tank/ds@a")
+            .resume_token("abc123")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn send_request_resume_token_excludes_stream_flags() {
+        assert!(SendRequestBuilder::default()
+            .resume_token("abc123")
+            .replicate(true)
+            .build()
+            .is_err());
+        assert!(SendRequestBuilder::default()
+            .resume_token("abc123")
+            .include_properties(true)
+            .build()
+            .is_err());
+        assert!(SendRequestBuilder::default()
+            .resume_token("abc123")
+            .raw(true)
+            .build()
+            .is_err());
+        assert!(SendRequestBuilder::default()
+            .resume_token("abc123")
+            .large_block(true)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn send_request_rejects_invalid_from() {
+        assert!(SendRequestBuilder::default()
+            .snapshot("tank/ds@b")
+            .from("tank/ds")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn parse_list_rows_splits_on_tab_and_preserves_spaces() {
+        let props = vec!["name".to_string(), "used".to_string()];
+        let rows = parse_list_rows(&props, "tank/ds one\t1024");
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get("name"), Some("tank/ds one"));
+        assert_eq!(rows[0].get_size("used"), Some(ByteSize(1024)));
+    }
+
+    #[test]
+    fn parse_list_rows_handles_multiple_lines() {
+        let props = vec!["name".to_string()];
+        let rows = parse_list_rows(&props, "tank/a\ntank/b");
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get("name"), Some("tank/a"));
+        assert_eq!(rows[1].get("name"), Some("tank/b"));
+    }
+
+    #[test]
+    fn list_row_get_returns_none_for_unrequested_property() {
+        let props = vec!["name".to_string()];
+        let rows = parse_list_rows(&props, "tank/a");
+
+        assert_eq!(rows[0].get("used"), None);
+    }
+
+    #[test]
+    fn volsize_accepts_human_string() {
+        let req = CreateRequestBuilder::default()
+            .name("tank/vol")
+            .volsize("10G")
+            .build()
+            .unwrap();
+
+        assert_eq!(req.volsize, Some(ByteSize::gb(10)));
+    }
+
+    #[test]
+    fn volsize_accepts_exact_bytesize_without_lossy_roundtrip() {
+        let req = CreateRequestBuilder::default()
+            .name("tank/vol")
+            .volsize(ByteSize(1_234_567))
+            .build()
+            .unwrap();
+
+        assert_eq!(req.volsize, Some(ByteSize(1_234_567)));
+    }
+
+    #[test]
+    fn volsize_rejects_garbage_string() {
+        assert!(CreateRequestBuilder::default()
+            .name("tank/vol")
+            .volsize("10 gigs")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn blocksize_rejects_garbage_string() {
+        assert!(CreateRequestBuilder::default()
+            .name("tank/vol")
+            .blocksize("not a size")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn snapshot_rollback_and_lifecycle_ops() {
+        let snap = Snapshot {
+            name: "tank/ds@a".to_string(),
+        };
+
+        assert!(snap.rollback(false).is_ok());
+        assert!(snap.rollback(true).is_ok());
+        assert!(snap.hold("keep").is_ok());
+        assert!(snap.release("keep").is_ok());
+    }
+
+    #[test]
+    fn snapshot_hold_and_release_reject_empty_tag() {
+        let snap = Snapshot {
+            name: "tank/ds@a".to_string(),
+        };
+
+        assert!(snap.hold("").is_err());
+        assert!(snap.release("").is_err());
+    }
+
+    #[test]
+    fn snapshot_rename_keeps_dataset_prefix() {
+        let snap = Snapshot {
+            name: "tank/ds@a".to_string(),
+        };
+
+        let renamed = snap.rename("b").unwrap();
+
+        assert_eq!(renamed.name(), "tank/ds@b");
+    }
+
+    #[test]
+    fn snapshot_bookmark_uses_hash_separator() {
+        let snap = Snapshot {
+            name: "tank/ds@a".to_string(),
+        };
+
+        let bookmark = snap.bookmark("bm").unwrap();
+
+        assert_eq!(bookmark.name(), "tank/ds#bm");
+    }
+
+    #[test]
+    fn parse_properties_tracks_source() {
+        let props = parse_properties(
+            "compression\tlz4\tlocal\natime\ton\tinherited from tank\nrecordsize\t128K\tdefault\n",
+        )
+        .unwrap();
+
+        assert_eq!(props["compression"].value, "lz4");
+        assert_eq!(props["compression"].source, PropertySource::Local);
+        assert_eq!(
+            props["atime"].source,
+            PropertySource::Inherited("tank".to_string())
+        );
+        assert_eq!(props["recordsize"].source, PropertySource::Default);
+    }
+
+    #[test]
+    fn parse_properties_rejects_unknown_source() {
+        assert!(parse_properties("compression\tlz4\tbogus\n").is_err());
+    }
+
+    #[test]
+    fn property_source_parses_all_variants() {
+        assert_eq!(
+            "local".parse::<PropertySource>().unwrap(),
+            PropertySource::Local
+        );
+        assert_eq!(
+            "default".parse::<PropertySource>().unwrap(),
+            PropertySource::Default
+        );
+        assert_eq!(
+            "received".parse::<PropertySource>().unwrap(),
+            PropertySource::Received
+        );
+        assert_eq!("-".parse::<PropertySource>().unwrap(), PropertySource::None_);
+        assert_eq!(
+            "inherited from tank/parent".parse::<PropertySource>().unwrap(),
+            PropertySource::Inherited("tank/parent".to_string())
+        );
+        assert!("garbage".parse::<PropertySource>().is_err());
+    }
 }
@@ -0,0 +1,420 @@
+#[cfg(not(test))]
+use crate::executor::{Binary, Executor};
+use crate::zfs::ZfsBuilderError;
+use derive_builder::Builder;
+use getset::Getters;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default)]
+struct PoolProperties(HashMap<String, String>);
+
+impl From<PoolProperties> for Vec<String> {
+    fn from(val: PoolProperties) -> Self {
+        val.0
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect()
+    }
+}
+
+/// A single group of devices making up part of a pool's vdev layout
+#[derive(Debug, Clone)]
+pub enum VdevGroup {
+    /// A plain stripe; each device is its own top-level vdev
+    Stripe(Vec<String>),
+    /// A mirrored vdev
+    Mirror(Vec<String>),
+    /// A single-parity RAIDZ vdev
+    RaidZ(Vec<String>),
+    /// A double-parity RAIDZ2 vdev
+    RaidZ2(Vec<String>),
+    /// A triple-parity RAIDZ3 vdev
+    RaidZ3(Vec<String>),
+}
+
+impl VdevGroup {
+    fn into_args(self) -> Vec<String> {
+        match self {
+            VdevGroup::Stripe(devices) => devices,
+            VdevGroup::Mirror(devices) => prefixed("mirror", devices),
+            VdevGroup::RaidZ(devices) => prefixed("raidz", devices),
+            VdevGroup::RaidZ2(devices) => prefixed("raidz2", devices),
+            VdevGroup::RaidZ3(devices) => prefixed("raidz3", devices),
+        }
+    }
+}
+
+fn prefixed(keyword: &str, devices: Vec<String>) -> Vec<String> {
+    let mut args = vec![String::from(keyword)];
+    args.extend(devices);
+    args
+}
+
+/// A request to create a new pool
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate", error = "ZfsBuilderError"))]
+pub struct PoolCreateRequest {
+    /// Name to give the new pool
+    #[builder(setter(into))]
+    name: String,
+
+    /// The vdev groups making up the pool, in the order they should be
+    /// passed to `zpool create`
+    #[builder(setter(custom), default)]
+    vdevs: Vec<VdevGroup>,
+
+    /// Pool properties, set with `-o`
+    #[builder(setter(custom), default)]
+    pool_properties: PoolProperties,
+
+    /// Root filesystem properties, set with `-O`
+    #[builder(setter(custom), default)]
+    filesystem_properties: PoolProperties,
+
+    /// Force creation even if a device appears to be in use or belong to
+    /// another pool
+    #[builder(default)]
+    force: bool,
+}
+
+impl PoolCreateRequestBuilder {
+    /// Add a vdev group (stripe, mirror or raidz) to the pool's layout
+    pub fn add_vdev_group(&mut self, group: VdevGroup) -> &mut Self {
+        if let Some(mut vdevs) = self.vdevs.clone() {
+            vdevs.push(group);
+            self.vdevs = Some(vdevs);
+        } else {
+            self.vdevs = Some(vec![group]);
+        }
+
+        self
+    }
+
+    /// Define a pool property that should be set with `-o`
+    pub fn add_pool_property<S: ToString>(&mut self, key: S, value: S) -> &mut Self {
+        if let Some(mut properties) = self.pool_properties.clone() {
+            properties.0.insert(key.to_string(), value.to_string());
+            self.pool_properties = Some(properties);
+        } else {
+            self.pool_properties = Some(PoolProperties(HashMap::from([(
+                key.to_string(),
+                value.to_string(),
+            )])));
+        }
+
+        self
+    }
+
+    /// Define a root filesystem property that should be set with `-O`
+    pub fn add_filesystem_property<S: ToString>(&mut self, key: S, value: S) -> &mut Self {
+        if let Some(mut properties) = self.filesystem_properties.clone() {
+            properties.0.insert(key.to_string(), value.to_string());
+            self.filesystem_properties = Some(properties);
+        } else {
+            self.filesystem_properties = Some(PoolProperties(HashMap::from([(
+                key.to_string(),
+                value.to_string(),
+            )])));
+        }
+
+        self
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        if let Some(name) = &self.name {
+            if name.contains('@') {
+                return Err("Invalid pool name".to_string());
+            }
+        }
+
+        match &self.vdevs {
+            Some(vdevs) if !vdevs.is_empty() => {}
+            _ => return Err("A pool needs at least one vdev".to_string()),
+        }
+
+        Ok(())
+    }
+}
+
+pub fn create(req: &PoolCreateRequest) -> crate::Result<Pool> {
+    let mut args = vec![];
+
+    if req.force {
+        args.push(String::from("-f"));
+    }
+
+    let pool_props: Vec<String> = req.pool_properties.clone().into();
+    for p in pool_props {
+        args.push(String::from("-o"));
+        args.push(p);
+    }
+
+    let fs_props: Vec<String> = req.filesystem_properties.clone().into();
+    for p in fs_props {
+        args.push(String::from("-O"));
+        args.push(p);
+    }
+
+    args.push(req.name.clone());
+
+    for group in req.vdevs.clone() {
+        args.extend(group.into_args());
+    }
+
+    zpool(ZpoolCommand::Create, args).map(|_v| Pool {
+        name: req.name.clone(),
+    })
+}
+
+pub fn destroy(name: &str) -> crate::Result<()> {
+    zpool(ZpoolCommand::Destroy, &[name]).map(|_v| ())
+}
+
+pub fn scrub(name: &str) -> crate::Result<()> {
+    zpool(ZpoolCommand::Scrub, &[name]).map(|_v| ())
+}
+
+/// List the names of all imported pools
+pub fn list() -> crate::Result<Vec<String>> {
+    zpool(ZpoolCommand::List, &["-Ho", "name"]).map(|v| v.lines().map(String::from).collect())
+}
+
+/// The status of a single leaf device within a pool's vdev tree.
+/// Vdev group headers (`mirror-0`, `raidzN-0`, `logs`, `cache`, `spares`,
+/// ...) are not reported here, only the real devices underneath them
+#[derive(Getters, Debug, Clone)]
+pub struct VdevStatus {
+    #[getset(get = "pub")]
+    path: String,
+    #[getset(get = "pub")]
+    state: String,
+    #[getset(get = "pub")]
+    read_errors: u64,
+    #[getset(get = "pub")]
+    write_errors: u64,
+    #[getset(get = "pub")]
+    checksum_errors: u64,
+}
+
+/// The parsed result of `zpool status`
+#[derive(Getters, Debug, Clone)]
+pub struct PoolStatus {
+    #[getset(get = "pub")]
+    name: String,
+    #[getset(get = "pub")]
+    state: String,
+    #[getset(get = "pub")]
+    vdevs: Vec<VdevStatus>,
+}
+
+pub fn status(name: &str) -> crate::Result<PoolStatus> {
+    let output = zpool(ZpoolCommand::Status, &["-P", name])?;
+    Ok(parse_status(name, &output))
+}
+
+fn parse_status(name: &str, output: &str) -> PoolStatus {
+    let mut state = String::new();
+    let mut vdevs = vec![];
+    let mut in_config = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+
+        if let Some(s) = trimmed.strip_prefix("state:") {
+            state = s.trim().to_string();
+            continue;
+        }
+
+        if trimmed == "config:" {
+            in_config = true;
+            continue;
+        }
+
+        // Any other `key:` line (e.g. the trailing `errors:`) ends the
+        // config section
+        if trimmed
+            .split_whitespace()
+            .next()
+            .map_or(false, |f| f.ends_with(':'))
+        {
+            in_config = false;
+            continue;
+        }
+
+        if !in_config || trimmed.is_empty() || trimmed.starts_with("NAME") {
+            continue;
+        }
+
+        let fields: Vec<&str> = trimmed.split_whitespace().collect();
+
+        // Skip the pool's own summary line and vdev group headers
+        // (mirror-0, raidzN-0, logs, cache, spares, ...); only real leaf
+        // devices should end up in `vdevs`
+        if fields.len() < 5 || fields[0] == name || is_vdev_group_label(fields[0]) {
+            continue;
+        }
+
+        vdevs.push(VdevStatus {
+            path: fields[0].to_string(),
+            state: fields[1].to_string(),
+            read_errors: fields[2].parse().unwrap_or(0),
+            write_errors: fields[3].parse().unwrap_or(0),
+            checksum_errors: fields[4].parse().unwrap_or(0),
+        });
+    }
+
+    PoolStatus {
+        name: name.to_string(),
+        state,
+        vdevs,
+    }
+}
+
+/// Whether `label` names a vdev group (a `mirror`/`raidz`/`spare`/
+/// `replacing` grouping, or a `logs`/`cache`/`spares`/`special`/`dedup`
+/// device class) rather than a real leaf device path
+fn is_vdev_group_label(label: &str) -> bool {
+    label.starts_with("mirror-")
+        || label.starts_with("raidz1-")
+        || label.starts_with("raidz2-")
+        || label.starts_with("raidz3-")
+        || label.starts_with("spare-")
+        || label.starts_with("replacing-")
+        || matches!(label, "logs" | "cache" | "spares" | "special" | "dedup")
+}
+
+enum ZpoolCommand {
+    Create,
+    Destroy,
+    Status,
+    List,
+    Scrub,
+}
+
+#[cfg(not(test))]
+fn zpool<I>(zpool_cmd: ZpoolCommand, args: I) -> crate::Result<String>
+where
+    I: IntoIterator,
+    I::Item: ToString,
+{
+    run_zpool(&*crate::executor::default_executor(), zpool_cmd, args)
+}
+
+#[cfg(not(test))]
+fn run_zpool<I>(executor: &dyn Executor, zpool_cmd: ZpoolCommand, args: I) -> crate::Result<String>
+where
+    I: IntoIterator,
+    I::Item: ToString,
+{
+    let subcommand = match zpool_cmd {
+        ZpoolCommand::Create => "create",
+        ZpoolCommand::Destroy => "destroy",
+        ZpoolCommand::Status => "status",
+        ZpoolCommand::List => "list",
+        ZpoolCommand::Scrub => "scrub",
+    };
+
+    let arg_strings: Vec<String> = args.into_iter().map(|a| a.to_string()).collect();
+    let mut full_args = vec![subcommand];
+    full_args.extend(arg_strings.iter().map(String::as_str));
+
+    let mut cmd = executor.command(Binary::Zpool, &full_args);
+
+    let output = cmd.output()?;
+
+    if !output.status.success() {
+        Err(crate::Error::ZpoolError(String::from_utf8(output.stderr)?))
+    } else {
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
+
+#[cfg(test)]
+fn zpool<I>(zpool_cmd: ZpoolCommand, _args: I) -> crate::Result<String>
+where
+    I: IntoIterator,
+    I::Item: ToString,
+{
+    match zpool_cmd {
+        ZpoolCommand::Create => Ok(String::new()),
+        ZpoolCommand::Destroy => Ok(String::new()),
+        ZpoolCommand::Status => Ok(String::new()),
+        ZpoolCommand::List => Ok(String::new()),
+        ZpoolCommand::Scrub => Ok(String::new()),
+    }
+}
+
+#[derive(Getters, Debug, Clone)]
+pub struct Pool {
+    #[getset(get = "pub")]
+    name: String,
+}
+
+impl Pool {
+    pub fn destroy(&self) -> crate::Result<()> {
+        destroy(&self.name)
+    }
+
+    pub fn scrub(&self) -> crate::Result<()> {
+        scrub(&self.name)
+    }
+
+    pub fn status(&self) -> crate::Result<PoolStatus> {
+        status(&self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_status_extracts_pool_state_and_vdevs() {
+        let output = "  pool: tank\n state: ONLINE\nconfig:\n\n\tNAME        STATE     READ WRITE CKSUM\n\ttank        ONLINE       0     0     0\n\t  /dev/sda1 ONLINE       0     0     0\n\t  /dev/sdb1 ONLINE       0     0     0\n";
+
+        let status = parse_status("tank", output);
+
+        assert_eq!(status.name(), "tank");
+        assert_eq!(status.state(), "ONLINE");
+        assert_eq!(status.vdevs().len(), 2);
+        assert_eq!(status.vdevs()[0].path(), "/dev/sda1");
+        assert_eq!(status.vdevs()[0].state(), "ONLINE");
+    }
+
+    #[test]
+    fn parse_status_reports_degraded_vdev_errors() {
+        let output = "state: DEGRADED\nconfig:\n\tNAME        STATE     READ WRITE CKSUM\n\ttank        DEGRADED     0     0     0\n\t  /dev/sda1 FAULTED      4     1     2\n";
+
+        let status = parse_status("tank", output);
+
+        assert_eq!(status.state(), "DEGRADED");
+        assert_eq!(status.vdevs()[0].state(), "FAULTED");
+        assert_eq!(*status.vdevs()[0].read_errors(), 4);
+        assert_eq!(*status.vdevs()[0].write_errors(), 1);
+        assert_eq!(*status.vdevs()[0].checksum_errors(), 2);
+    }
+
+    #[test]
+    fn parse_status_ignores_lines_outside_config_section() {
+        let output = "state: ONLINE\nerrors: No known data errors\nconfig:\n\tNAME STATE READ WRITE CKSUM\n\ttank ONLINE 0 0 0\n\t  /dev/sda1 ONLINE 0 0 0\n";
+
+        let status = parse_status("tank", output);
+
+        assert_eq!(status.vdevs().len(), 1);
+    }
+
+    #[test]
+    fn parse_status_skips_mirror_and_raidz_group_headers() {
+        let output = "state: ONLINE\nconfig:\n\tNAME          STATE     READ WRITE CKSUM\n\ttank          ONLINE       0     0     0\n\t  mirror-0    ONLINE       0     0     0\n\t    /dev/sda1 ONLINE       0     0     0\n\t    /dev/sdb1 ONLINE       0     0     0\n\t  raidz1-0    ONLINE       0     0     0\n\t    /dev/sdc1 ONLINE       0     0     0\n\t    /dev/sdd1 ONLINE       0     0     0\n\t    /dev/sde1 ONLINE       0     0     0\n\tlogs\n\t  /dev/sdf1   ONLINE       0     0     0\n";
+
+        let status = parse_status("tank", output);
+
+        let paths: Vec<&str> = status.vdevs().iter().map(|v| v.path().as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/dev/sda1", "/dev/sdb1", "/dev/sdc1", "/dev/sdd1", "/dev/sde1", "/dev/sdf1"
+            ]
+        );
+    }
+}